@@ -12,33 +12,25 @@ use aes_gcm::{
 };
 use argon2::{password_hash::SaltString, Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use colllab_claude::jwt::{AuthError, Claims};
+use jsonwebtoken::{encode, EncodingKey, Header};
 use rand::RngCore;
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
+fn main() {
+    // This file is illustrative only: it exists to show every @collab
+    // annotation form the parser supports, not to be run.
+}
+
 // ============================================
 // SINGLE-LINE ANNOTATIONS
 // ============================================
 
-// @collab trust="READ_ONLY" owner="security-team"
-pub fn validate_jwt(token: &str, secret: &[u8]) -> Result<Claims, AuthError> {
-    // This entire function is READ_ONLY
-    // Claude cannot modify this code directly
-    let validation = Validation::default();
-    let key = DecodingKey::from_secret(secret);
-
-    let token_data = decode::<Claims>(token, &key, &validation)
-        .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
-
-    if token_data.claims.exp < Utc::now().timestamp() as usize {
-        return Err(AuthError::TokenExpired);
-    }
-
-    Ok(token_data.claims)
-}
+// validate_jwt is READ_ONLY (owner="security-team") and now lives in
+// `colllab_claude::jwt` so the identity-provider layer can reuse it
+// instead of re-implementing JWT validation.
 
 // @collab trust="SUGGEST_ONLY" owner="payments-team"
 pub async fn process_payment(
@@ -372,13 +364,6 @@ impl UserService {
 // TYPE DEFINITIONS
 // ============================================
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Claims {
-    pub sub: String,
-    pub exp: usize,
-    pub iat: usize,
-}
-
 #[derive(Debug)]
 pub struct PaymentResult {
     pub success: bool,
@@ -417,21 +402,7 @@ pub struct ProfileUpdates {
     pub email: Option<String>,
 }
 
-// Error types
-#[derive(Debug, Error)]
-pub enum AuthError {
-    #[error("User not found")]
-    UserNotFound,
-    #[error("Invalid password")]
-    InvalidPassword,
-    #[error("Token expired")]
-    TokenExpired,
-    #[error("Invalid token: {0}")]
-    InvalidToken(String),
-    #[error("Token generation failed: {0}")]
-    TokenGenerationFailed(String),
-}
-
+// Error types (AuthError now lives in `colllab_claude::jwt`)
 #[derive(Debug, Error)]
 pub enum PaymentError {
     #[error("Charge failed: {0}")]
@@ -476,6 +447,7 @@ pub enum UserError {
 
 // Placeholder types (would be defined elsewhere)
 pub struct Database;
+#[derive(Clone)]
 pub struct Transaction;
 pub struct AuditLog;
 pub struct StripeClient;
@@ -504,7 +476,6 @@ impl Transaction {
     pub fn user_data(&self) -> UserDataRepository { unimplemented!() }
     pub async fn commit(&self) -> Result<(), TransactionError> { unimplemented!() }
     pub async fn rollback(&self) -> Result<(), TransactionError> { unimplemented!() }
-    pub fn clone(&self) -> Self { unimplemented!() }
 }
 
 impl AuditLog {
@@ -515,3 +486,33 @@ pub struct UserRepository;
 pub struct AccountRepository;
 pub struct PostRepository;
 pub struct UserDataRepository;
+
+impl UserRepository {
+    pub async fn find_by_email(&self, _email: &str) -> Result<Option<User>, AuthError> { unimplemented!() }
+    pub async fn find_by_id(&self, _user_id: &str) -> Result<Option<User>, UserError> { unimplemented!() }
+    pub async fn update(&self, _user: &User) -> Result<(), UserError> { unimplemented!() }
+    pub async fn delete(&self, _user_id: &str) -> Result<(), TransactionError> { unimplemented!() }
+}
+
+impl AccountRepository {
+    pub async fn find_by_id_for_update(&self, _account_id: &str) -> Result<Option<Account>, TransactionError> { unimplemented!() }
+    pub async fn update_balance(&self, _account_id: &str, _balance: i64) -> Result<(), TransactionError> { unimplemented!() }
+}
+
+impl PostRepository {
+    pub async fn delete_by_author_id(&self, _user_id: &str) -> Result<(), TransactionError> { unimplemented!() }
+}
+
+impl UserDataRepository {
+    pub async fn delete_by_user_id(&self, _user_id: &str) -> Result<(), TransactionError> { unimplemented!() }
+}
+
+impl StripeClient {
+    pub fn charges(&self) -> ChargesClient { unimplemented!() }
+}
+
+pub struct ChargesClient;
+
+impl ChargesClient {
+    pub async fn create(&self, _params: ChargeParams) -> Result<Charge, PaymentError> { unimplemented!() }
+}
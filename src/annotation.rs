@@ -0,0 +1,548 @@
+//! Parsing and validation for `@collab` annotations.
+//!
+//! See `examples/rust.rs` for the supported forms: single-line
+//! (`// @collab trust="..."`), multi-line (consecutive `// @collab key=...`
+//! comments that merge into one annotation), and block (`@collab:begin` /
+//! `@collab:end`). Today a malformed annotation is silently misread rather
+//! than rejected; [`validate`] turns every way that can go wrong into a
+//! typed, accumulated [`AnnotationError`] so a linter can report them all
+//! at once instead of stopping at the first.
+
+use crate::proposal_store::TrustLevel;
+use std::collections::HashMap;
+
+/// Byte-offset span of the annotation text that produced an error or a
+/// resolved annotation, for pointing an editor/linter at the right spot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+/// A fully parsed `@collab` annotation, merged from one or more comment
+/// lines and attached to either a single line or a `:begin`/`:end` block.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub trust: TrustLevel,
+    pub owner: Option<String>,
+    pub intent: Option<String>,
+    pub constraints: Vec<String>,
+    pub span: Span,
+    pub kind: AnnotationKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationKind {
+    /// Applies to the single item (function/struct/etc.) immediately following.
+    SingleLine,
+    /// Applies to everything between the `:begin` and its matching `:end`.
+    Block,
+}
+
+/// All annotations found in a source file, in source order.
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationMap {
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationError {
+    pub span: Span,
+    pub kind: AnnotationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnnotationErrorKind {
+    /// `@collab:begin` with no matching `@collab:end` before end of file.
+    UnmatchedBegin,
+    /// `@collab:end` with no open `@collab:begin`.
+    UnmatchedEnd,
+    /// A `@collab:begin` was found while another block was already open.
+    NestedBegin,
+    /// `trust="..."` used a value other than READ_ONLY/SUGGEST_ONLY/SUPERVISED/AUTONOMOUS.
+    UnknownTrustValue(String),
+    /// The same key was given two different values within one merged annotation.
+    DuplicateConflictingKey(String),
+    /// `constraints=[...]` did not parse as a JSON-style array of strings.
+    MalformedConstraints(String),
+    /// An annotation had no `trust=` key at all.
+    MissingTrust,
+}
+
+fn line_span(line_no: usize, byte_offset: usize, line: &str) -> Span {
+    Span {
+        start_line: line_no,
+        end_line: line_no,
+        start_byte: byte_offset,
+        end_byte: byte_offset + line.len(),
+    }
+}
+
+fn parse_trust(value: &str) -> Option<TrustLevel> {
+    match value {
+        "READ_ONLY" => Some(TrustLevel::ReadOnly),
+        "SUGGEST_ONLY" => Some(TrustLevel::SuggestOnly),
+        "SUPERVISED" => Some(TrustLevel::Supervised),
+        "AUTONOMOUS" => Some(TrustLevel::Autonomous),
+        _ => None,
+    }
+}
+
+/// Parse `key="value"` / `key=[...]` pairs out of the text following
+/// `@collab` (or `@collab:begin`) on one line. Values are everything
+/// inside the first matching quote/bracket pair for that key.
+fn parse_attrs(rest: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let bytes = rest.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        while i < bytes.len() && (bytes[i] as char).is_whitespace() {
+            i += 1;
+        }
+        let key_start = i;
+        while i < bytes.len() && bytes[i] != b'=' {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            break;
+        }
+        let key = rest[key_start..i].trim().to_string();
+        i += 1; // skip '='
+
+        if i >= bytes.len() {
+            break;
+        }
+
+        let value = if bytes[i] == b'"' {
+            let start = i + 1;
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += 1;
+            }
+            let value = rest[start..end].to_string();
+            i = end + 1;
+            value
+        } else if bytes[i] == b'[' {
+            let start = i;
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b']' {
+                end += 1;
+            }
+            end = (end + 1).min(bytes.len());
+            let value = rest[start..end].to_string();
+            i = end;
+            value
+        } else {
+            let start = i;
+            let mut end = start;
+            while end < bytes.len() && !(bytes[end] as char).is_whitespace() {
+                end += 1;
+            }
+            let value = rest[start..end].to_string();
+            i = end;
+            value
+        };
+
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+
+    attrs
+}
+
+/// Parse a `constraints=[...]` value into a list of strings, or report why
+/// it isn't one.
+fn parse_constraints(raw: &str) -> Result<Vec<String>, String> {
+    let trimmed = raw.trim();
+    let inner = trimmed
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| raw.to_string())?;
+
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut constraints = Vec::new();
+    for part in inner.split(',') {
+        let part = part.trim();
+        let unquoted = part
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .ok_or_else(|| raw.to_string())?;
+        constraints.push(unquoted.to_string());
+    }
+    Ok(constraints)
+}
+
+/// The attributes resolved for one annotation after merging its (possibly
+/// multi-line) `key=value` pairs.
+struct MergedAttrs {
+    trust: TrustLevel,
+    owner: Option<String>,
+    intent: Option<String>,
+    constraints: Vec<String>,
+}
+
+/// Merge consecutive `// @collab ...` lines (not `:begin`/`:end`) into one
+/// raw attribute set, per the multi-line merge rule.
+fn merge_attrs(
+    raw_attrs: Vec<(String, String)>,
+    span: Span,
+) -> Result<MergedAttrs, AnnotationError> {
+    let mut trust = None;
+    let mut owner = None;
+    let mut intent = None;
+    let mut constraints = Vec::new();
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    for (key, value) in raw_attrs {
+        if let Some(existing) = seen.get(&key) {
+            if existing != &value {
+                return Err(AnnotationError {
+                    span,
+                    kind: AnnotationErrorKind::DuplicateConflictingKey(key),
+                });
+            }
+            continue;
+        }
+        seen.insert(key.clone(), value.clone());
+
+        match key.as_str() {
+            "trust" => {
+                trust = Some(parse_trust(&value).ok_or_else(|| AnnotationError {
+                    span,
+                    kind: AnnotationErrorKind::UnknownTrustValue(value.clone()),
+                })?);
+            }
+            "owner" => owner = Some(value),
+            "intent" => intent = Some(value),
+            "constraints" => {
+                constraints = parse_constraints(&value).map_err(|raw| AnnotationError {
+                    span,
+                    kind: AnnotationErrorKind::MalformedConstraints(raw),
+                })?;
+            }
+            _ => {}
+        }
+    }
+
+    let trust = trust.ok_or(AnnotationError {
+        span,
+        kind: AnnotationErrorKind::MissingTrust,
+    })?;
+
+    Ok(MergedAttrs {
+        trust,
+        owner,
+        intent,
+        constraints,
+    })
+}
+
+/// Find the last line (0-indexed) of the item that starts at `start_line`
+/// (skipping any leading blank/comment lines), by brace-depth matching.
+/// Used to extend a single-line annotation's reach over the whole
+/// function/struct/impl it applies to, not just the comment itself.
+fn find_item_extent(lines: &[&str], mut i: usize) -> usize {
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            i += 1;
+            continue;
+        }
+        break;
+    }
+    if i >= lines.len() {
+        return lines.len().saturating_sub(1);
+    }
+
+    let mut depth = 0i32;
+    let mut opened = false;
+    let mut j = i;
+    while j < lines.len() {
+        for ch in lines[j].chars() {
+            match ch {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                ';' if !opened && depth == 0 => return j,
+                _ => {}
+            }
+        }
+        if opened && depth <= 0 {
+            return j;
+        }
+        j += 1;
+    }
+    j.saturating_sub(1)
+}
+
+/// Parse and validate every `@collab` annotation in `source`, accumulating
+/// every error found rather than stopping at the first.
+pub fn validate(source: &str) -> Result<AnnotationMap, Vec<AnnotationError>> {
+    let mut annotations = Vec::new();
+    let mut errors = Vec::new();
+
+    let lines: Vec<&str> = source.lines().collect();
+    let mut line_byte_starts = Vec::with_capacity(lines.len());
+    let mut offset = 0usize;
+    for line in &lines {
+        line_byte_starts.push(offset);
+        offset += line.len() + 1;
+    }
+
+    let mut open_block: Option<(Vec<(String, String)>, Span)> = None;
+    // Depth of `:begin`s nested inside the current open block, each of
+    // which already produced a `NestedBegin` error. Their matching `:end`s
+    // must be consumed here too, so they don't get mistaken for the real
+    // close of `open_block`.
+    let mut nested_depth: u32 = 0;
+    let mut pending_single: Vec<(String, String)> = Vec::new();
+    let mut pending_span: Option<Span> = None;
+
+    for (line_no, line) in lines.iter().enumerate() {
+        let line = *line;
+        let byte_offset = line_byte_starts[line_no];
+        let trimmed = line.trim_start();
+        let comment_body = trimmed.strip_prefix("//").map(str::trim_start);
+
+        if let Some(body) = comment_body {
+            if let Some(rest) = body.strip_prefix("@collab:begin") {
+                if open_block.is_some() {
+                    errors.push(AnnotationError {
+                        span: line_span(line_no, byte_offset, line),
+                        kind: AnnotationErrorKind::NestedBegin,
+                    });
+                    nested_depth += 1;
+                } else {
+                    open_block = Some((parse_attrs(rest), line_span(line_no, byte_offset, line)));
+                }
+                continue;
+            }
+
+            if body.strip_prefix("@collab:end").is_some() {
+                if nested_depth > 0 {
+                    // Consume the nested block's own `:end` as a unit;
+                    // it doesn't close `open_block`.
+                    nested_depth -= 1;
+                    continue;
+                }
+                match open_block.take() {
+                    Some((attrs, start_span)) => {
+                        let span = Span {
+                            start_line: start_span.start_line,
+                            end_line: line_no,
+                            start_byte: start_span.start_byte,
+                            end_byte: byte_offset + line.len(),
+                        };
+                        match merge_attrs(attrs, span) {
+                            Ok(merged) => {
+                                annotations.push(Annotation {
+                                    trust: merged.trust,
+                                    owner: merged.owner,
+                                    intent: merged.intent,
+                                    constraints: merged.constraints,
+                                    span,
+                                    kind: AnnotationKind::Block,
+                                });
+                            }
+                            Err(err) => errors.push(err),
+                        }
+                    }
+                    None => errors.push(AnnotationError {
+                        span: line_span(line_no, byte_offset, line),
+                        kind: AnnotationErrorKind::UnmatchedEnd,
+                    }),
+                }
+                continue;
+            }
+
+            if let Some(rest) = body.strip_prefix("@collab") {
+                let span = line_span(line_no, byte_offset, line);
+                pending_span = Some(match pending_span {
+                    Some(existing) => Span {
+                        end_line: span.end_line,
+                        end_byte: span.end_byte,
+                        ..existing
+                    },
+                    None => span,
+                });
+                pending_single.extend(parse_attrs(rest));
+                continue;
+            }
+        }
+
+        // Non-`@collab` line: flush any pending single-line annotation,
+        // widening its span to cover the whole item it applies to.
+        if !pending_single.is_empty() {
+            let comment_span = pending_span.take().unwrap();
+            let item_end = find_item_extent(&lines, line_no);
+            let span = Span {
+                end_line: item_end,
+                end_byte: line_byte_starts[item_end] + lines[item_end].len(),
+                ..comment_span
+            };
+            match merge_attrs(std::mem::take(&mut pending_single), span) {
+                Ok(merged) => {
+                    annotations.push(Annotation {
+                        trust: merged.trust,
+                        owner: merged.owner,
+                        intent: merged.intent,
+                        constraints: merged.constraints,
+                        span,
+                        kind: AnnotationKind::SingleLine,
+                    });
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+    }
+
+    if let Some((_, span)) = open_block {
+        errors.push(AnnotationError {
+            span,
+            kind: AnnotationErrorKind::UnmatchedBegin,
+        });
+    }
+
+    if !pending_single.is_empty() {
+        let comment_span = pending_span.unwrap();
+        let item_end = find_item_extent(&lines, comment_span.end_line + 1);
+        let span = Span {
+            end_line: item_end,
+            end_byte: line_byte_starts[item_end] + lines[item_end].len(),
+            ..comment_span
+        };
+        match merge_attrs(pending_single, span) {
+            Ok(merged) => {
+                annotations.push(Annotation {
+                    trust: merged.trust,
+                    owner: merged.owner,
+                    intent: merged.intent,
+                    constraints: merged.constraints,
+                    span,
+                    kind: AnnotationKind::SingleLine,
+                });
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(AnnotationMap { annotations })
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_annotation_extends_over_the_following_fn() {
+        let source = "// @collab trust=\"SUGGEST_ONLY\" owner=\"payments-team\"\npub fn charge() {\n    1;\n}\n";
+        let map = validate(source).unwrap();
+        assert_eq!(map.annotations.len(), 1);
+        let annotation = &map.annotations[0];
+        assert_eq!(annotation.kind, AnnotationKind::SingleLine);
+        assert_eq!(annotation.trust, TrustLevel::SuggestOnly);
+        assert_eq!(annotation.owner.as_deref(), Some("payments-team"));
+        assert_eq!(annotation.span.end_line, 3);
+    }
+
+    #[test]
+    fn multi_line_comments_merge_into_one_annotation() {
+        let source = "// @collab trust=\"SUPERVISED\"\n// @collab owner=\"security-team\"\npub fn f() {}\n";
+        let map = validate(source).unwrap();
+        assert_eq!(map.annotations.len(), 1);
+        let annotation = &map.annotations[0];
+        assert_eq!(annotation.trust, TrustLevel::Supervised);
+        assert_eq!(annotation.owner.as_deref(), Some("security-team"));
+    }
+
+    #[test]
+    fn block_annotation_spans_begin_to_end() {
+        let source = "// @collab:begin trust=\"AUTONOMOUS\"\nfn a() {}\nfn b() {}\n// @collab:end\n";
+        let map = validate(source).unwrap();
+        assert_eq!(map.annotations.len(), 1);
+        let annotation = &map.annotations[0];
+        assert_eq!(annotation.kind, AnnotationKind::Block);
+        assert_eq!(annotation.trust, TrustLevel::Autonomous);
+        assert_eq!(annotation.span.start_line, 0);
+        assert_eq!(annotation.span.end_line, 3);
+    }
+
+    #[test]
+    fn nested_begin_does_not_corrupt_the_outer_block() {
+        let source = "// @collab:begin trust=\"SUPERVISED\"\n// @collab:begin trust=\"AUTONOMOUS\"\nfn a() {}\n// @collab:end\nfn b() {}\n// @collab:end\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, AnnotationErrorKind::NestedBegin);
+    }
+
+    #[test]
+    fn unmatched_begin_is_reported() {
+        let source = "// @collab:begin trust=\"SUPERVISED\"\nfn a() {}\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, AnnotationErrorKind::UnmatchedBegin);
+    }
+
+    #[test]
+    fn unmatched_end_is_reported() {
+        let source = "fn a() {}\n// @collab:end\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, AnnotationErrorKind::UnmatchedEnd);
+    }
+
+    #[test]
+    fn unknown_trust_value_is_reported() {
+        let source = "// @collab trust=\"SOMETIMES\"\nfn a() {}\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind,
+            AnnotationErrorKind::UnknownTrustValue("SOMETIMES".to_string())
+        );
+    }
+
+    #[test]
+    fn duplicate_conflicting_key_is_reported() {
+        let source =
+            "// @collab trust=\"SUPERVISED\" owner=\"team-a\"\n// @collab owner=\"team-b\"\nfn a() {}\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind,
+            AnnotationErrorKind::DuplicateConflictingKey("owner".to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_constraints_is_reported() {
+        let source = "// @collab trust=\"SUPERVISED\" constraints=\"not-an-array\"\nfn a() {}\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].kind,
+            AnnotationErrorKind::MalformedConstraints(_)
+        ));
+    }
+
+    #[test]
+    fn missing_trust_is_reported() {
+        let source = "// @collab owner=\"team-a\"\nfn a() {}\n";
+        let errors = validate(source).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, AnnotationErrorKind::MissingTrust);
+    }
+}
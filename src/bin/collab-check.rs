@@ -0,0 +1,79 @@
+//! CLI entry point for the `collab-check` pre-merge gate.
+//!
+//! Usage: `collab-check <annotated-source-file> <unified-diff-file> [approved-proposal-id ...]`
+//!
+//! `<annotated-source-file>` is matched against the path the diff's
+//! `+++ b/...` line refers to (e.g. `examples/rust.rs`), after stripping a
+//! leading `./` from either side, since hunks for any other file in the
+//! diff are ignored.
+//!
+//! Prints the [`CheckReport`] as JSON to stdout and exits non-zero if any
+//! hunk violates its region's trust level, so it can run as a CI stage and
+//! have its stdout uploaded as a pipeline artifact.
+
+use std::process::ExitCode;
+
+use colllab_claude::annotation::validate;
+use colllab_claude::collab_check::{check, normalize_path, parse_unified_diff};
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+
+    let (Some(source_path), Some(diff_path)) = (args.next(), args.next()) else {
+        eprintln!("usage: collab-check <source-file> <diff-file> [approved-proposal-id ...]");
+        return ExitCode::FAILURE;
+    };
+    let approved_proposal_ids: Vec<String> = args.collect();
+
+    let source = match std::fs::read_to_string(&source_path) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("failed to read {source_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let diff = match std::fs::read_to_string(&diff_path) {
+        Ok(diff) => diff,
+        Err(err) => {
+            eprintln!("failed to read {diff_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let annotations = match validate(&source) {
+        Ok(annotations) => annotations,
+        Err(errors) => {
+            eprintln!("{source_path} has malformed @collab annotations: {errors:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let hunks = parse_unified_diff(&diff);
+    let annotated_file = normalize_path(&source_path);
+
+    if !hunks.is_empty()
+        && !hunks
+            .iter()
+            .any(|hunk| normalize_path(&hunk.file) == annotated_file)
+    {
+        eprintln!(
+            "warning: {diff_path} has no hunks for {annotated_file}; \
+             it touches {:?} instead. Check that <source-file> matches \
+             the diff's `+++ b/...` path, or this run checked nothing.",
+            hunks.iter().map(|h| &h.file).collect::<Vec<_>>()
+        );
+    }
+
+    let report = check(&hunks, annotated_file, &annotations, &approved_proposal_ids);
+
+    match report.to_json() {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("failed to serialize report: {err}"),
+    }
+
+    if report.exit_code() == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
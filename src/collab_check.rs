@@ -0,0 +1,392 @@
+//! `collab-check`: a CI gate that fails a diff for violating trust levels.
+//!
+//! `@collab` annotations are only documentation until something enforces
+//! them. This module takes a unified diff and an [`AnnotationMap`] and
+//! reports every changed hunk that touches a READ_ONLY region (e.g.
+//! `validate_jwt`, the crypto-team `EncryptionService` block) or modifies a
+//! SUGGEST_ONLY/SUPERVISED region (e.g. `process_payment`, `delete_user`)
+//! without a corresponding approved proposal id, so it can run as a
+//! pre-merge pipeline stage.
+
+use serde::Serialize;
+
+use crate::annotation::AnnotationMap;
+use crate::proposal_store::TrustLevel;
+use crate::trust_resolver::TrustResolver;
+
+/// One contiguous range of changed lines in one file, as found in a
+/// unified diff's `@@ -l,s +l,s @@` hunk header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Proposal id this hunk claims to satisfy, if the diff/commit message
+    /// recorded one (e.g. via a `Proposal-Id:` trailer).
+    pub proposal_id: Option<String>,
+}
+
+/// Why one hunk was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum ViolationReason {
+    /// The region is READ_ONLY; it cannot be touched at all.
+    ReadOnlyEdit,
+    /// The region requires review but no approved proposal id was given.
+    MissingApproval,
+    /// A proposal id was given but it isn't in the approved set.
+    UnapprovedProposal { proposal_id: String },
+}
+
+/// A single hunk that violates its region's trust level.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub trust_level: TrustLevel,
+    pub owner: Option<String>,
+    pub reason: ViolationReason,
+}
+
+/// Machine-readable report, suitable for uploading as a pipeline artifact.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct CheckReport {
+    pub violations: Vec<Violation>,
+}
+
+impl CheckReport {
+    /// Per the requirement that this run as a pre-merge gate: non-zero
+    /// when anything was rejected.
+    pub fn exit_code(&self) -> i32 {
+        if self.violations.is_empty() {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Strip a leading `./` so `./examples/rust.rs` and `examples/rust.rs`
+/// compare equal, regardless of which form the caller or the diff uses.
+pub fn normalize_path(path: &str) -> &str {
+    path.trim_start_matches("./")
+}
+
+/// Check a set of diff hunks against an [`AnnotationMap`] parsed from
+/// `annotated_file`, flagging every hunk in that file that edits a
+/// READ_ONLY region, or a SUGGEST_ONLY/SUPERVISED region without a
+/// proposal id present in `approved_proposal_ids`. Hunks against other
+/// files in the diff are ignored, since `annotations` says nothing about
+/// their trust levels. Both sides are compared via [`normalize_path`], so
+/// a hunk's `file` and `annotated_file` need not match byte-for-byte.
+///
+/// Trust is resolved per line via [`TrustResolver`], so a line's
+/// *effective* trust (innermost single-line override, then enclosing
+/// block, then file default — see `trust_resolver`) governs, not every
+/// annotation whose span happens to overlap the hunk. Without this, an
+/// AUTONOMOUS override nested inside a READ_ONLY/SUPERVISED block (e.g.
+/// `delete_user` inside `UserService`) would be flagged by the enclosing
+/// block's annotation even though the override permits the edit.
+pub fn check(
+    hunks: &[DiffHunk],
+    annotated_file: &str,
+    annotations: &AnnotationMap,
+    approved_proposal_ids: &[String],
+) -> CheckReport {
+    let mut violations = Vec::new();
+    let resolver = TrustResolver::new(annotations);
+    let annotated_file = normalize_path(annotated_file);
+
+    for hunk in hunks {
+        if normalize_path(&hunk.file) != annotated_file {
+            continue;
+        }
+
+        let mut reasons: Vec<(TrustLevel, Option<String>, ViolationReason)> = Vec::new();
+
+        for line in hunk.start_line..=hunk.end_line {
+            let effective = resolver.resolve(line);
+
+            let reason = match effective.level {
+                TrustLevel::ReadOnly => Some(ViolationReason::ReadOnlyEdit),
+                TrustLevel::SuggestOnly | TrustLevel::Supervised => match &hunk.proposal_id {
+                    None => Some(ViolationReason::MissingApproval),
+                    Some(id) if approved_proposal_ids.iter().any(|a| a == id) => None,
+                    Some(id) => Some(ViolationReason::UnapprovedProposal {
+                        proposal_id: id.clone(),
+                    }),
+                },
+                TrustLevel::Autonomous => None,
+            };
+
+            if let Some(reason) = reason {
+                let key = (effective.level, effective.owner.clone(), reason);
+                if !reasons.contains(&key) {
+                    reasons.push(key);
+                }
+            }
+        }
+
+        for (trust_level, owner, reason) in reasons {
+            violations.push(Violation {
+                file: hunk.file.clone(),
+                start_line: hunk.start_line,
+                end_line: hunk.end_line,
+                trust_level,
+                owner,
+                reason,
+            });
+        }
+    }
+
+    CheckReport { violations }
+}
+
+/// Parse a unified diff's `@@ -l,s +l,s @@` hunks into [`DiffHunk`]s,
+/// reading the new-file line range (the `+l,s` side) since that's what an
+/// annotation map built from the post-change source describes.
+///
+/// A `Proposal-Id: <id>` trailer anywhere in the diff text is associated
+/// with every hunk of the file it appears under.
+pub fn parse_unified_diff(diff: &str) -> Vec<DiffHunk> {
+    let mut hunks = Vec::new();
+    let mut current_file = String::new();
+    let mut current_proposal_id: Option<String> = None;
+
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("+++ ") {
+            current_file = rest.trim_start_matches("b/").to_string();
+            current_proposal_id = None;
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("Proposal-Id:") {
+            current_proposal_id = Some(rest.trim().to_string());
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("@@ ") {
+            let Some(new_range) = rest.split(' ').find(|part| part.starts_with('+')) else {
+                continue;
+            };
+            let new_range = &new_range[1..];
+            let mut parts = new_range.splitn(2, ',');
+            let Some(start) = parts.next().and_then(|s| s.parse::<usize>().ok()) else {
+                continue;
+            };
+            let len = parts
+                .next()
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or(1);
+            let end = start + len.saturating_sub(1);
+
+            hunks.push(DiffHunk {
+                file: current_file.clone(),
+                start_line: start.saturating_sub(1),
+                end_line: end.saturating_sub(1),
+                proposal_id: current_proposal_id.clone(),
+            });
+        }
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotation::{Annotation, AnnotationKind, Span};
+
+    fn annotation(trust: TrustLevel, start_line: usize, end_line: usize) -> Annotation {
+        Annotation {
+            trust,
+            owner: Some("payments-team".to_string()),
+            intent: None,
+            constraints: Vec::new(),
+            span: Span {
+                start_line,
+                end_line,
+                start_byte: 0,
+                end_byte: 0,
+            },
+            kind: AnnotationKind::SingleLine,
+        }
+    }
+
+    fn block_annotation(trust: TrustLevel, start_line: usize, end_line: usize) -> Annotation {
+        Annotation {
+            trust,
+            owner: Some("payments-team".to_string()),
+            intent: None,
+            constraints: Vec::new(),
+            span: Span {
+                start_line,
+                end_line,
+                start_byte: 0,
+                end_byte: 0,
+            },
+            kind: AnnotationKind::Block,
+        }
+    }
+
+    fn hunk(file: &str, start_line: usize, end_line: usize, proposal_id: Option<&str>) -> DiffHunk {
+        DiffHunk {
+            file: file.to_string(),
+            start_line,
+            end_line,
+            proposal_id: proposal_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn hunks_in_other_files_are_ignored() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::ReadOnly, 0, 5)],
+        };
+        let hunks = vec![hunk("other.rs", 0, 5, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn editing_a_read_only_region_is_always_a_violation() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::ReadOnly, 0, 5)],
+        };
+        let hunks = vec![hunk("src/lib.rs", 2, 3, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert_eq!(report.violations.len(), 1);
+        assert!(matches!(
+            report.violations[0].reason,
+            ViolationReason::ReadOnlyEdit
+        ));
+    }
+
+    #[test]
+    fn suggest_only_without_a_proposal_id_is_a_violation() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::SuggestOnly, 0, 5)],
+        };
+        let hunks = vec![hunk("src/lib.rs", 2, 3, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert_eq!(report.violations.len(), 1);
+        assert!(matches!(
+            report.violations[0].reason,
+            ViolationReason::MissingApproval
+        ));
+    }
+
+    #[test]
+    fn suggest_only_with_an_unapproved_proposal_id_is_a_violation() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::SuggestOnly, 0, 5)],
+        };
+        let hunks = vec![hunk("src/lib.rs", 2, 3, Some("prop-1"))];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &["prop-2".to_string()]);
+        assert_eq!(report.violations.len(), 1);
+        assert!(matches!(
+            &report.violations[0].reason,
+            ViolationReason::UnapprovedProposal { proposal_id } if proposal_id == "prop-1"
+        ));
+    }
+
+    #[test]
+    fn suggest_only_with_an_approved_proposal_id_passes() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::SuggestOnly, 0, 5)],
+        };
+        let hunks = vec![hunk("src/lib.rs", 2, 3, Some("prop-1"))];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &["prop-1".to_string()]);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn autonomous_regions_never_violate() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::Autonomous, 0, 5)],
+        };
+        let hunks = vec![hunk("src/lib.rs", 2, 3, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn an_inner_autonomous_override_is_not_flagged_by_its_enclosing_read_only_block() {
+        let annotations = AnnotationMap {
+            annotations: vec![
+                block_annotation(TrustLevel::ReadOnly, 0, 20),
+                block_annotation(TrustLevel::Autonomous, 5, 10),
+            ],
+        };
+        let hunks = vec![hunk("src/lib.rs", 6, 8, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn a_hunk_spanning_an_override_and_its_enclosing_block_is_reported_once_per_region() {
+        let annotations = AnnotationMap {
+            annotations: vec![
+                block_annotation(TrustLevel::ReadOnly, 0, 20),
+                block_annotation(TrustLevel::Autonomous, 5, 10),
+            ],
+        };
+        // Spans both the autonomous override (5-10) and the surrounding
+        // read-only block (0-20, outside the override).
+        let hunks = vec![hunk("src/lib.rs", 2, 15, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert_eq!(report.violations.len(), 1);
+        assert!(matches!(
+            report.violations[0].reason,
+            ViolationReason::ReadOnlyEdit
+        ));
+    }
+
+    #[test]
+    fn parse_unified_diff_reads_new_file_ranges_and_proposal_trailer() {
+        let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\nProposal-Id: prop-1\n@@ -10,2 +10,3 @@\n context\n+added\n context\n";
+        let hunks = parse_unified_diff(diff);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].file, "src/lib.rs");
+        assert_eq!(hunks[0].start_line, 9);
+        assert_eq!(hunks[0].end_line, 11);
+        assert_eq!(hunks[0].proposal_id.as_deref(), Some("prop-1"));
+    }
+
+    #[test]
+    fn proposal_id_does_not_bleed_across_file_headers() {
+        let diff = "--- a/a.rs\n+++ b/a.rs\nProposal-Id: prop-approved\n@@ -1,1 +1,1 @@\n-old\n+new\n--- a/b.rs\n+++ b/b.rs\n@@ -1,1 +1,1 @@\n-old\n+new\n";
+        let hunks = parse_unified_diff(diff);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].proposal_id.as_deref(), Some("prop-approved"));
+        assert_eq!(hunks[1].file, "b.rs");
+        assert_eq!(hunks[1].proposal_id, None);
+    }
+
+    #[test]
+    fn check_matches_hunk_and_annotated_file_paths_regardless_of_a_leading_dot_slash() {
+        let annotations = AnnotationMap {
+            annotations: vec![annotation(TrustLevel::ReadOnly, 0, 5)],
+        };
+        let hunks = vec![hunk("./src/lib.rs", 2, 3, None)];
+
+        let report = check(&hunks, "src/lib.rs", &annotations, &[]);
+        assert_eq!(report.violations.len(), 1);
+
+        let hunks = vec![hunk("src/lib.rs", 2, 3, None)];
+        let report = check(&hunks, "./src/lib.rs", &annotations, &[]);
+        assert_eq!(report.violations.len(), 1);
+    }
+}
@@ -0,0 +1,267 @@
+//! Identity-bound approval for `@collab` proposals.
+//!
+//! `owner="security-team"` / `owner="payments-team"` annotations are only
+//! labels until approving a [`Proposal`](crate::proposal_store::Proposal)
+//! actually requires an authenticated principal who belongs to that team.
+//! [`IdentityProvider`] resolves a raw credential (a JWT, a static token,
+//! ...) to an [`Approver`] and its team memberships; [`decide_proposal`] is
+//! the proposal-decision entry point that enforces this — it's the
+//! sanctioned way to approve or reject a proposal. Calling
+//! [`ProposalStore::record_decision`] directly bypasses the check, the same
+//! way editing the filesystem out from under any other store would.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::jwt::{validate_jwt, AuthError, Claims};
+use crate::proposal_store::{Decision, Proposal, ProposalStore, ProposalStoreError};
+
+/// An authenticated principal and the teams it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approver {
+    pub identity: String,
+    pub teams: Vec<String>,
+}
+
+impl Approver {
+    pub fn is_member_of(&self, team: &str) -> bool {
+        self.teams.iter().any(|t| t == team)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum IdentityError {
+    #[error("authentication failed: {0}")]
+    AuthFailed(String),
+    #[error("identity {0} has no known team memberships")]
+    UnknownIdentity(String),
+}
+
+/// Resolves a raw credential to an authenticated [`Approver`].
+pub trait IdentityProvider: Send + Sync {
+    fn resolve(&self, credential: &str) -> Result<Approver, IdentityError>;
+}
+
+/// Looks up team membership from a fixed identity -> teams map, e.g. one
+/// loaded from a static team-map config file.
+pub struct StaticTeamMapProvider {
+    teams_by_identity: HashMap<String, Vec<String>>,
+}
+
+impl StaticTeamMapProvider {
+    pub fn new(teams_by_identity: HashMap<String, Vec<String>>) -> Self {
+        Self { teams_by_identity }
+    }
+}
+
+impl IdentityProvider for StaticTeamMapProvider {
+    fn resolve(&self, credential: &str) -> Result<Approver, IdentityError> {
+        let teams = self
+            .teams_by_identity
+            .get(credential)
+            .ok_or_else(|| IdentityError::UnknownIdentity(credential.to_string()))?;
+        Ok(Approver {
+            identity: credential.to_string(),
+            teams: teams.clone(),
+        })
+    }
+}
+
+/// Resolves an approver from a JWT, mapping the token's `sub` claim to a
+/// team membership lookup via an inner [`StaticTeamMapProvider`]-style map.
+/// Reuses [`validate_jwt`] so JWT validation stays in one place.
+pub struct JwtIdentityProvider {
+    secret: Vec<u8>,
+    teams_by_subject: HashMap<String, Vec<String>>,
+}
+
+impl JwtIdentityProvider {
+    pub fn new(secret: Vec<u8>, teams_by_subject: HashMap<String, Vec<String>>) -> Self {
+        Self {
+            secret,
+            teams_by_subject,
+        }
+    }
+
+    fn claims(&self, token: &str) -> Result<Claims, AuthError> {
+        validate_jwt(token, &self.secret)
+    }
+}
+
+impl IdentityProvider for JwtIdentityProvider {
+    fn resolve(&self, credential: &str) -> Result<Approver, IdentityError> {
+        let claims = self
+            .claims(credential)
+            .map_err(|e| IdentityError::AuthFailed(e.to_string()))?;
+        let teams = self
+            .teams_by_subject
+            .get(&claims.sub)
+            .ok_or_else(|| IdentityError::UnknownIdentity(claims.sub.clone()))?;
+        Ok(Approver {
+            identity: claims.sub,
+            teams: teams.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ApprovalError {
+    #[error("{approver} is not a member of owning team {owner}")]
+    NotInOwningTeam { approver: String, owner: String },
+}
+
+/// Reject an approval where the approver's teams don't include the
+/// proposal's `owner`. Only applies to approvals: rejecting a proposal
+/// (flagging it as unwanted) isn't owner-scoped the same way.
+fn authorize_approval(approver: &Approver, proposal: &Proposal) -> Result<(), ApprovalError> {
+    if approver.is_member_of(&proposal.owner) {
+        Ok(())
+    } else {
+        Err(ApprovalError::NotInOwningTeam {
+            approver: approver.identity.clone(),
+            owner: proposal.owner.clone(),
+        })
+    }
+}
+
+/// A reviewer's verdict, before it's bound to an authenticated approver.
+pub enum Verdict {
+    Approve,
+    Reject { reason: String },
+}
+
+#[derive(Debug, Error)]
+pub enum DecisionError {
+    #[error(transparent)]
+    Approval(#[from] ApprovalError),
+    #[error(transparent)]
+    Store(#[from] ProposalStoreError),
+}
+
+/// The proposal-decision entry point: authenticate `approver` via an
+/// [`IdentityProvider`] first, then call this to record their verdict.
+/// Approving a proposal is rejected unless `approver`'s teams include the
+/// proposal's `owner`; rejecting one is not owner-scoped. This is the only
+/// path that should be used to decide a proposal — calling
+/// [`ProposalStore::record_decision`] directly skips the owner check
+/// entirely.
+pub fn decide_proposal(
+    store: &dyn ProposalStore,
+    id: &str,
+    approver: &Approver,
+    verdict: Verdict,
+    at: i64,
+) -> Result<(), DecisionError> {
+    let decision = match verdict {
+        Verdict::Approve => {
+            let proposal = store.get_proposal(id)?;
+            authorize_approval(approver, &proposal)?;
+            Decision::Approve {
+                approver: approver.identity.clone(),
+                at,
+            }
+        }
+        Verdict::Reject { reason } => Decision::Reject {
+            approver: approver.identity.clone(),
+            reason,
+            at,
+        },
+    };
+    store.record_decision(id, decision)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proposal_store::{InMemoryProposalStore, ProposalStatus, TrustLevel};
+
+    fn sample_proposal(owner: &str) -> Proposal {
+        Proposal {
+            id: "prop-1".to_string(),
+            owner: owner.to_string(),
+            target: "src/lib.rs".to_string(),
+            trust_level: TrustLevel::SuggestOnly,
+            intent: None,
+            constraints: Vec::new(),
+            diff: String::new(),
+            status: ProposalStatus::Pending,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn static_team_map_resolves_known_identities() {
+        let provider = StaticTeamMapProvider::new(HashMap::from([(
+            "alice".to_string(),
+            vec!["payments-team".to_string()],
+        )]));
+
+        let approver = provider.resolve("alice").unwrap();
+        assert!(approver.is_member_of("payments-team"));
+        assert!(!approver.is_member_of("security-team"));
+    }
+
+    #[test]
+    fn static_team_map_rejects_unknown_identities() {
+        let provider = StaticTeamMapProvider::new(HashMap::new());
+        let err = provider.resolve("ghost").unwrap_err();
+        assert!(matches!(err, IdentityError::UnknownIdentity(_)));
+    }
+
+    #[test]
+    fn approving_as_an_owning_team_member_succeeds() {
+        let store = InMemoryProposalStore::new();
+        store.put_proposal(sample_proposal("payments-team")).unwrap();
+        let approver = Approver {
+            identity: "alice".to_string(),
+            teams: vec!["payments-team".to_string()],
+        };
+
+        decide_proposal(&store, "prop-1", &approver, Verdict::Approve, 1).unwrap();
+
+        let proposal = store.get_proposal("prop-1").unwrap();
+        assert!(matches!(proposal.status, ProposalStatus::Approved { .. }));
+    }
+
+    #[test]
+    fn approving_outside_the_owning_team_is_rejected() {
+        let store = InMemoryProposalStore::new();
+        store.put_proposal(sample_proposal("payments-team")).unwrap();
+        let approver = Approver {
+            identity: "mallory".to_string(),
+            teams: vec!["security-team".to_string()],
+        };
+
+        let err = decide_proposal(&store, "prop-1", &approver, Verdict::Approve, 1).unwrap_err();
+        assert!(matches!(err, DecisionError::Approval(_)));
+
+        let proposal = store.get_proposal("prop-1").unwrap();
+        assert!(matches!(proposal.status, ProposalStatus::Pending));
+    }
+
+    #[test]
+    fn rejecting_is_not_owner_scoped() {
+        let store = InMemoryProposalStore::new();
+        store.put_proposal(sample_proposal("payments-team")).unwrap();
+        let approver = Approver {
+            identity: "mallory".to_string(),
+            teams: vec!["security-team".to_string()],
+        };
+
+        decide_proposal(
+            &store,
+            "prop-1",
+            &approver,
+            Verdict::Reject {
+                reason: "not needed".to_string(),
+            },
+            1,
+        )
+        .unwrap();
+
+        let proposal = store.get_proposal("prop-1").unwrap();
+        assert!(matches!(proposal.status, ProposalStatus::Rejected { .. }));
+    }
+}
@@ -0,0 +1,46 @@
+//! JWT validation and claims, shared between the example auth flow
+//! (`examples/rust.rs`) and [`crate::identity`]'s JWT-backed
+//! [`crate::identity::IdentityProvider`].
+
+use chrono::Utc;
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: usize,
+    pub iat: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Invalid password")]
+    InvalidPassword,
+    #[error("Token expired")]
+    TokenExpired,
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+    #[error("Token generation failed: {0}")]
+    TokenGenerationFailed(String),
+}
+
+// @collab trust="READ_ONLY" owner="security-team"
+pub fn validate_jwt(token: &str, secret: &[u8]) -> Result<Claims, AuthError> {
+    // This entire function is READ_ONLY
+    // Claude cannot modify this code directly
+    let validation = Validation::default();
+    let key = DecodingKey::from_secret(secret);
+
+    let token_data = decode::<Claims>(token, &key, &validation)
+        .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+
+    if token_data.claims.exp < Utc::now().timestamp() as usize {
+        return Err(AuthError::TokenExpired);
+    }
+
+    Ok(token_data.claims)
+}
@@ -0,0 +1,14 @@
+//! Core library for the @collab annotation system.
+//!
+//! This crate implements the tooling that gives `@collab` trust annotations
+//! (see `examples/rust.rs`) real teeth: durable proposal storage, a signed
+//! audit trail, annotation parsing/validation, a CI enforcement gate, and
+//! trust resolution.
+
+pub mod annotation;
+pub mod audit;
+pub mod collab_check;
+pub mod identity;
+pub mod jwt;
+pub mod proposal_store;
+pub mod trust_resolver;
@@ -0,0 +1,435 @@
+//! Durable storage for @collab proposals.
+//!
+//! SUGGEST_ONLY and SUPERVISED regions (see `examples/rust.rs`, e.g.
+//! `UserService::delete_user`) require a human-reviewed proposal before
+//! Claude's edit can land. Previously that review state lived only in a
+//! single session; [`ProposalStore`] gives it a durable, swappable home so
+//! a team can share pending proposals and decision history across runs.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The trust level governing a region annotated with `@collab`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustLevel {
+    ReadOnly,
+    SuggestOnly,
+    Supervised,
+    Autonomous,
+}
+
+/// Unique identifier for a proposal.
+pub type ProposalId = String;
+
+/// A proposed edit to a SUGGEST_ONLY or SUPERVISED region, awaiting review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: ProposalId,
+    pub owner: String,
+    pub target: String,
+    pub trust_level: TrustLevel,
+    pub intent: Option<String>,
+    pub constraints: Vec<String>,
+    pub diff: String,
+    pub status: ProposalStatus,
+    pub created_at: i64,
+}
+
+/// Review status of a [`Proposal`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    Pending,
+    Approved { approver: String, at: i64 },
+    Rejected { approver: String, reason: String, at: i64 },
+}
+
+/// A reviewer's decision on a pending proposal.
+#[derive(Debug, Clone)]
+pub enum Decision {
+    Approve { approver: String, at: i64 },
+    Reject { approver: String, reason: String, at: i64 },
+}
+
+#[derive(Debug, Error)]
+pub enum ProposalStoreError {
+    #[error("proposal not found: {0}")]
+    NotFound(ProposalId),
+    #[error("proposal already decided: {0}")]
+    AlreadyDecided(ProposalId),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Persistence for proposals and their review decisions.
+///
+/// Implementations must be safe to share across threads; Claude sessions
+/// and CI jobs may read and write concurrently.
+pub trait ProposalStore: Send + Sync {
+    /// Store a newly created proposal.
+    fn put_proposal(&self, proposal: Proposal) -> Result<(), ProposalStoreError>;
+
+    /// Fetch a single proposal by id.
+    fn get_proposal(&self, id: &str) -> Result<Proposal, ProposalStoreError>;
+
+    /// List all proposals still awaiting review for the given owning team.
+    fn list_pending(&self, owner: &str) -> Result<Vec<Proposal>, ProposalStoreError>;
+
+    /// Record a reviewer's decision against an existing pending proposal.
+    fn record_decision(&self, id: &str, decision: Decision) -> Result<(), ProposalStoreError>;
+}
+
+/// In-memory [`ProposalStore`], suitable for tests and single-process use.
+#[derive(Default)]
+pub struct InMemoryProposalStore {
+    proposals: Mutex<HashMap<ProposalId, Proposal>>,
+}
+
+impl InMemoryProposalStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ProposalStore for InMemoryProposalStore {
+    fn put_proposal(&self, proposal: Proposal) -> Result<(), ProposalStoreError> {
+        self.proposals
+            .lock()
+            .unwrap()
+            .insert(proposal.id.clone(), proposal);
+        Ok(())
+    }
+
+    fn get_proposal(&self, id: &str) -> Result<Proposal, ProposalStoreError> {
+        self.proposals
+            .lock()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| ProposalStoreError::NotFound(id.to_string()))
+    }
+
+    fn list_pending(&self, owner: &str) -> Result<Vec<Proposal>, ProposalStoreError> {
+        Ok(self
+            .proposals
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|p| p.owner == owner && matches!(p.status, ProposalStatus::Pending))
+            .cloned()
+            .collect())
+    }
+
+    fn record_decision(&self, id: &str, decision: Decision) -> Result<(), ProposalStoreError> {
+        let mut proposals = self.proposals.lock().unwrap();
+        let proposal = proposals
+            .get_mut(id)
+            .ok_or_else(|| ProposalStoreError::NotFound(id.to_string()))?;
+
+        if !matches!(proposal.status, ProposalStatus::Pending) {
+            return Err(ProposalStoreError::AlreadyDecided(id.to_string()));
+        }
+
+        proposal.status = match decision {
+            Decision::Approve { approver, at } => ProposalStatus::Approved { approver, at },
+            Decision::Reject {
+                approver,
+                reason,
+                at,
+            } => ProposalStatus::Rejected {
+                approver,
+                reason,
+                at,
+            },
+        };
+        Ok(())
+    }
+}
+
+/// Filesystem-backed [`ProposalStore`], one JSON file per proposal under `root`.
+pub struct FileProposalStore {
+    root: PathBuf,
+}
+
+impl FileProposalStore {
+    /// Open (and create if missing) a proposal store rooted at `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, ProposalStoreError> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        Ok(Self { root })
+    }
+
+    fn path_for(&self, id: &str) -> PathBuf {
+        self.root.join(format!("{id}.json"))
+    }
+}
+
+impl ProposalStore for FileProposalStore {
+    fn put_proposal(&self, proposal: Proposal) -> Result<(), ProposalStoreError> {
+        let path = self.path_for(&proposal.id);
+        let contents = serde_json::to_vec_pretty(&proposal)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    fn get_proposal(&self, id: &str) -> Result<Proposal, ProposalStoreError> {
+        let path = self.path_for(id);
+        let contents = std::fs::read(&path)
+            .map_err(|_| ProposalStoreError::NotFound(id.to_string()))?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    fn list_pending(&self, owner: &str) -> Result<Vec<Proposal>, ProposalStoreError> {
+        let mut pending = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if entry.path().extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let contents = std::fs::read(entry.path())?;
+            let proposal: Proposal = serde_json::from_slice(&contents)?;
+            if proposal.owner == owner && matches!(proposal.status, ProposalStatus::Pending) {
+                pending.push(proposal);
+            }
+        }
+        Ok(pending)
+    }
+
+    fn record_decision(&self, id: &str, decision: Decision) -> Result<(), ProposalStoreError> {
+        let mut proposal = self.get_proposal(id)?;
+        if !matches!(proposal.status, ProposalStatus::Pending) {
+            return Err(ProposalStoreError::AlreadyDecided(id.to_string()));
+        }
+        proposal.status = match decision {
+            Decision::Approve { approver, at } => ProposalStatus::Approved { approver, at },
+            Decision::Reject {
+                approver,
+                reason,
+                at,
+            } => ProposalStatus::Rejected {
+                approver,
+                reason,
+                at,
+            },
+        };
+        self.put_proposal(proposal)
+    }
+}
+
+/// S3-compatible (or any `object_store`-compatible) [`ProposalStore`].
+///
+/// Backed by the `object_store` crate so the same code works against AWS
+/// S3, GCS, Azure Blob, or a local filesystem shim in tests. Each proposal
+/// is stored as a JSON object under `prefix/<id>.json`; `list_pending`
+/// lists the prefix and filters client-side, since object stores don't
+/// offer server-side JSON field queries.
+///
+/// `object_store`'s HTTP-backed implementations (S3, GCS, Azure) need a
+/// running Tokio reactor to drive their async I/O, so this keeps its own
+/// single-threaded runtime around to `block_on` against rather than
+/// `futures::executor::block_on`, which has no reactor and panics the
+/// moment a real backend tries to make a network call.
+pub struct S3ProposalStore {
+    client: Box<dyn object_store::ObjectStore>,
+    prefix: object_store::path::Path,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl S3ProposalStore {
+    pub fn new(
+        client: Box<dyn object_store::ObjectStore>,
+        prefix: impl AsRef<str>,
+    ) -> Result<Self, ProposalStoreError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ProposalStoreError::Backend(e.to_string()))?;
+        Ok(Self {
+            client,
+            prefix: object_store::path::Path::from(prefix.as_ref()),
+            runtime,
+        })
+    }
+
+    fn object_path(&self, id: &str) -> object_store::path::Path {
+        self.prefix.child(format!("{id}.json"))
+    }
+}
+
+impl ProposalStore for S3ProposalStore {
+    fn put_proposal(&self, proposal: Proposal) -> Result<(), ProposalStoreError> {
+        let bytes = serde_json::to_vec(&proposal)?;
+        let path = self.object_path(&proposal.id);
+        self.runtime
+            .block_on(self.client.put(&path, bytes.into()))
+            .map_err(|e| ProposalStoreError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    fn get_proposal(&self, id: &str) -> Result<Proposal, ProposalStoreError> {
+        let path = self.object_path(id);
+        let result = self
+            .runtime
+            .block_on(self.client.get(&path))
+            .map_err(|_| ProposalStoreError::NotFound(id.to_string()))?;
+        let bytes = self
+            .runtime
+            .block_on(result.bytes())
+            .map_err(|e| ProposalStoreError::Backend(e.to_string()))?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn list_pending(&self, owner: &str) -> Result<Vec<Proposal>, ProposalStoreError> {
+        let listing = self
+            .runtime
+            .block_on(async {
+                use futures::TryStreamExt;
+                self.client
+                    .list(Some(&self.prefix))
+                    .try_collect::<Vec<_>>()
+                    .await
+            })
+            .map_err(|e| ProposalStoreError::Backend(e.to_string()))?;
+
+        let mut pending = Vec::new();
+        for meta in listing {
+            let result = self
+                .runtime
+                .block_on(self.client.get(&meta.location))
+                .map_err(|e| ProposalStoreError::Backend(e.to_string()))?;
+            let bytes = self
+                .runtime
+                .block_on(result.bytes())
+                .map_err(|e| ProposalStoreError::Backend(e.to_string()))?;
+            let proposal: Proposal = serde_json::from_slice(&bytes)?;
+            if proposal.owner == owner && matches!(proposal.status, ProposalStatus::Pending) {
+                pending.push(proposal);
+            }
+        }
+        Ok(pending)
+    }
+
+    fn record_decision(&self, id: &str, decision: Decision) -> Result<(), ProposalStoreError> {
+        let mut proposal = self.get_proposal(id)?;
+        if !matches!(proposal.status, ProposalStatus::Pending) {
+            return Err(ProposalStoreError::AlreadyDecided(id.to_string()));
+        }
+        proposal.status = match decision {
+            Decision::Approve { approver, at } => ProposalStatus::Approved { approver, at },
+            Decision::Reject {
+                approver,
+                reason,
+                at,
+            } => ProposalStatus::Rejected {
+                approver,
+                reason,
+                at,
+            },
+        };
+        self.put_proposal(proposal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_proposal(id: &str) -> Proposal {
+        Proposal {
+            id: id.to_string(),
+            owner: "payments-team".to_string(),
+            target: "src/lib.rs".to_string(),
+            trust_level: TrustLevel::SuggestOnly,
+            intent: None,
+            constraints: Vec::new(),
+            diff: String::new(),
+            status: ProposalStatus::Pending,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn in_memory_store_round_trips_a_proposal() {
+        let store = InMemoryProposalStore::new();
+        store.put_proposal(sample_proposal("prop-1")).unwrap();
+
+        let fetched = store.get_proposal("prop-1").unwrap();
+        assert_eq!(fetched.id, "prop-1");
+    }
+
+    #[test]
+    fn in_memory_store_reports_missing_proposals() {
+        let store = InMemoryProposalStore::new();
+        let err = store.get_proposal("missing").unwrap_err();
+        assert!(matches!(err, ProposalStoreError::NotFound(_)));
+    }
+
+    #[test]
+    fn in_memory_store_lists_only_pending_proposals_for_the_owner() {
+        let store = InMemoryProposalStore::new();
+        store.put_proposal(sample_proposal("prop-1")).unwrap();
+        store
+            .record_decision(
+                "prop-1",
+                Decision::Approve {
+                    approver: "alice".to_string(),
+                    at: 1,
+                },
+            )
+            .unwrap();
+        store.put_proposal(sample_proposal("prop-2")).unwrap();
+
+        let pending = store.list_pending("payments-team").unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, "prop-2");
+    }
+
+    #[test]
+    fn recording_a_decision_twice_is_rejected() {
+        let store = InMemoryProposalStore::new();
+        store.put_proposal(sample_proposal("prop-1")).unwrap();
+        store
+            .record_decision(
+                "prop-1",
+                Decision::Approve {
+                    approver: "alice".to_string(),
+                    at: 1,
+                },
+            )
+            .unwrap();
+
+        let err = store
+            .record_decision(
+                "prop-1",
+                Decision::Reject {
+                    approver: "alice".to_string(),
+                    reason: "too late".to_string(),
+                    at: 2,
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, ProposalStoreError::AlreadyDecided(_)));
+    }
+
+    #[test]
+    fn file_store_round_trips_a_proposal_via_disk() {
+        let dir = std::env::temp_dir().join(format!(
+            "colllab-claude-proposal-store-test-{:?}",
+            std::thread::current().id()
+        ));
+        let store = FileProposalStore::new(&dir).unwrap();
+        store.put_proposal(sample_proposal("prop-1")).unwrap();
+
+        let fetched = store.get_proposal("prop-1").unwrap();
+        assert_eq!(fetched.id, "prop-1");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
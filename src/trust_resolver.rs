@@ -0,0 +1,257 @@
+//! Resolve the effective trust level governing an arbitrary source position.
+//!
+//! `examples/rust.rs` has layered semantics: `UserService` is block-level
+//! SUPERVISED, `delete_user` overrides to SUGGEST_ONLY, and
+//! `update_profile` falls back to the block. [`TrustResolver`] is the
+//! public API that computes this: given an [`AnnotationMap`] and a
+//! `(file, line)` position, it applies the documented precedence order —
+//! innermost single-line annotation, then the enclosing `:begin`/`:end`
+//! block, then the file default — merging attributes and propagating
+//! `constraints` from outer scopes inward along the way.
+
+use crate::annotation::{Annotation, AnnotationKind, AnnotationMap, Span};
+use crate::proposal_store::TrustLevel;
+
+/// The trust level and attributes governing one position, plus the
+/// annotation that produced them.
+#[derive(Debug, Clone)]
+pub struct EffectiveTrust {
+    pub level: TrustLevel,
+    pub owner: Option<String>,
+    pub intent: Option<String>,
+    pub constraints: Vec<String>,
+    pub source_annotation_span: Option<Span>,
+}
+
+/// Trust level applied to a position with no governing annotation at all.
+pub const FILE_DEFAULT_TRUST: TrustLevel = TrustLevel::Autonomous;
+
+/// One step in a resolution trace, in the order precedence was applied.
+#[derive(Debug, Clone)]
+pub struct ResolutionStep {
+    pub description: String,
+    pub annotation: Option<Annotation>,
+}
+
+/// The full trace of how a position's effective trust was computed, for
+/// debugging why a line got the trust level it did.
+#[derive(Debug, Clone)]
+pub struct ResolutionTrace {
+    pub steps: Vec<ResolutionStep>,
+    pub result: EffectiveTrust,
+}
+
+fn contains_line(span: &Span, line: usize) -> bool {
+    span.start_line <= line && line <= span.end_line
+}
+
+/// Resolves effective trust for positions in a single source file's
+/// [`AnnotationMap`].
+pub struct TrustResolver<'a> {
+    map: &'a AnnotationMap,
+}
+
+impl<'a> TrustResolver<'a> {
+    pub fn new(map: &'a AnnotationMap) -> Self {
+        Self { map }
+    }
+
+    /// Every annotation whose span covers `line`, split into the
+    /// innermost single-line override (if any) and the enclosing blocks,
+    /// outermost first.
+    fn governing(&self, line: usize) -> (Option<&'a Annotation>, Vec<&'a Annotation>) {
+        let mut single_line = None;
+        let mut blocks: Vec<&Annotation> = Vec::new();
+
+        for annotation in &self.map.annotations {
+            if !contains_line(&annotation.span, line) {
+                continue;
+            }
+            match annotation.kind {
+                AnnotationKind::SingleLine => single_line = Some(annotation),
+                AnnotationKind::Block => blocks.push(annotation),
+            }
+        }
+
+        // Narrower (later-starting) blocks are more deeply nested, so they
+        // take precedence; sort outermost first for inward constraint
+        // propagation.
+        blocks.sort_by_key(|a| a.span.start_line);
+
+        (single_line, blocks)
+    }
+
+    /// Compute the effective trust level at `line` (0-indexed, matching
+    /// [`Span`]'s line numbering).
+    pub fn resolve(&self, line: usize) -> EffectiveTrust {
+        self.explain(line).result
+    }
+
+    /// Compute the effective trust at `line`, with the full precedence
+    /// trace that produced it.
+    pub fn explain(&self, line: usize) -> ResolutionTrace {
+        let (single_line, blocks) = self.governing(line);
+
+        let mut steps = Vec::new();
+        let mut constraints: Vec<String> = Vec::new();
+        let mut level = FILE_DEFAULT_TRUST;
+        let mut owner = None;
+        let mut intent = None;
+        let mut source_annotation_span = None;
+
+        steps.push(ResolutionStep {
+            description: format!("file default: {level:?}"),
+            annotation: None,
+        });
+
+        for block in &blocks {
+            level = block.trust;
+            owner = block.owner.clone().or(owner);
+            intent = block.intent.clone().or(intent);
+            // Inner blocks inherit and may extend outer constraints.
+            for constraint in &block.constraints {
+                if !constraints.contains(constraint) {
+                    constraints.push(constraint.clone());
+                }
+            }
+            source_annotation_span = Some(block.span);
+            steps.push(ResolutionStep {
+                description: format!(
+                    "enclosing block (lines {}-{}): trust={:?}",
+                    block.span.start_line, block.span.end_line, block.trust
+                ),
+                annotation: Some((*block).clone()),
+            });
+        }
+
+        if let Some(single) = single_line {
+            level = single.trust;
+            owner = single.owner.clone().or(owner);
+            intent = single.intent.clone().or(intent);
+            for constraint in &single.constraints {
+                if !constraints.contains(constraint) {
+                    constraints.push(constraint.clone());
+                }
+            }
+            source_annotation_span = Some(single.span);
+            steps.push(ResolutionStep {
+                description: format!(
+                    "innermost single-line annotation (lines {}-{}): trust={:?}",
+                    single.span.start_line, single.span.end_line, single.trust
+                ),
+                annotation: Some(single.clone()),
+            });
+        }
+
+        let result = EffectiveTrust {
+            level,
+            owner,
+            intent,
+            constraints,
+            source_annotation_span,
+        };
+
+        ResolutionTrace { steps, result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start_line: usize, end_line: usize) -> Span {
+        Span {
+            start_line,
+            end_line,
+            start_byte: 0,
+            end_byte: 0,
+        }
+    }
+
+    fn block(trust: TrustLevel, start_line: usize, end_line: usize, constraints: &[&str]) -> Annotation {
+        Annotation {
+            trust,
+            owner: None,
+            intent: None,
+            constraints: constraints.iter().map(|c| c.to_string()).collect(),
+            span: span(start_line, end_line),
+            kind: AnnotationKind::Block,
+        }
+    }
+
+    fn single_line(trust: TrustLevel, start_line: usize, end_line: usize) -> Annotation {
+        Annotation {
+            trust,
+            owner: None,
+            intent: None,
+            constraints: Vec::new(),
+            span: span(start_line, end_line),
+            kind: AnnotationKind::SingleLine,
+        }
+    }
+
+    #[test]
+    fn a_line_with_no_governing_annotation_gets_the_file_default() {
+        let map = AnnotationMap::default();
+        let resolver = TrustResolver::new(&map);
+
+        let effective = resolver.resolve(0);
+        assert_eq!(effective.level, FILE_DEFAULT_TRUST);
+        assert!(effective.source_annotation_span.is_none());
+    }
+
+    #[test]
+    fn an_enclosing_block_overrides_the_file_default() {
+        let map = AnnotationMap {
+            annotations: vec![block(TrustLevel::Supervised, 0, 10, &[])],
+        };
+        let resolver = TrustResolver::new(&map);
+
+        let effective = resolver.resolve(5);
+        assert_eq!(effective.level, TrustLevel::Supervised);
+    }
+
+    #[test]
+    fn a_single_line_annotation_overrides_its_enclosing_block() {
+        let map = AnnotationMap {
+            annotations: vec![
+                block(TrustLevel::Supervised, 0, 10, &[]),
+                single_line(TrustLevel::SuggestOnly, 3, 5),
+            ],
+        };
+        let resolver = TrustResolver::new(&map);
+
+        assert_eq!(resolver.resolve(4).level, TrustLevel::SuggestOnly);
+        // Outside the single-line override, the block still governs.
+        assert_eq!(resolver.resolve(8).level, TrustLevel::Supervised);
+    }
+
+    #[test]
+    fn the_innermost_of_nested_blocks_wins() {
+        let map = AnnotationMap {
+            annotations: vec![
+                block(TrustLevel::Supervised, 0, 20, &[]),
+                block(TrustLevel::Autonomous, 5, 10, &[]),
+            ],
+        };
+        let resolver = TrustResolver::new(&map);
+
+        assert_eq!(resolver.resolve(7).level, TrustLevel::Autonomous);
+        assert_eq!(resolver.resolve(15).level, TrustLevel::Supervised);
+    }
+
+    #[test]
+    fn constraints_propagate_outward_in_and_accumulate() {
+        let map = AnnotationMap {
+            annotations: vec![
+                block(TrustLevel::Supervised, 0, 20, &["no-secrets"]),
+                block(TrustLevel::Autonomous, 5, 10, &["no-network"]),
+            ],
+        };
+        let resolver = TrustResolver::new(&map);
+
+        let effective = resolver.resolve(7);
+        assert!(effective.constraints.contains(&"no-secrets".to_string()));
+        assert!(effective.constraints.contains(&"no-network".to_string()));
+    }
+}